@@ -3,30 +3,115 @@ use axum::Json;
 use axum::extract::State;
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
+use base64::Engine;
+use bip39::{Language, Mnemonic, MnemonicType, Seed};
 use dotenv::dotenv;
+use hmac::{Hmac, Mac};
 use serde::Deserialize;
 use serde_json::json;
+use sha2::Sha512;
+use solana_account_decoder::{UiAccountData, UiAccountEncoding};
 use solana_client::rpc_client::RpcClient;
-use solana_sdk::commitment_config::CommitmentConfig;
+use solana_client::rpc_config::RpcSendTransactionConfig;
+use solana_client::rpc_request::TokenAccountsFilter;
+use solana_client::rpc_response::RpcSimulateTransactionResult;
+use solana_sdk::commitment_config::{CommitmentConfig, CommitmentLevel};
 use solana_sdk::program_pack::Pack;
 use solana_sdk::pubkey::Pubkey;
 use solana_sdk::signature::Signature;
 use solana_sdk::signer::Signer;
 use solana_sdk::signer::keypair::Keypair;
 use solana_sdk::{system_instruction, transaction::Transaction};
-use spl_associated_token_account::get_associated_token_address;
+use spl_associated_token_account::get_associated_token_address_with_program_id;
+use spl_associated_token_account::instruction::create_associated_token_account;
 use spl_token::instruction::transfer;
 use spl_token::{instruction as token_instruction, state::Mint};
+use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+use std::collections::HashMap;
 use std::str::FromStr;
 use std::sync::Arc;
 
 struct AppState {
     client: Arc<RpcClient>,
+    cluster: Cluster,
 }
 
 #[derive(Debug, Deserialize)]
 struct Config {
-    rpc: String,
+    #[serde(default)]
+    rpc: Option<String>,
+    #[serde(default)]
+    cluster: Option<String>,
+}
+
+/// The Solana network a request targets. Resolves to an RPC URL and a
+/// commitment level so transfers and queries never silently cross networks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Cluster {
+    Mainnet,
+    Devnet,
+    Testnet,
+    Localnet,
+    Custom(String),
+}
+
+impl Cluster {
+    fn rpc_url(&self) -> String {
+        match self {
+            Cluster::Mainnet => "https://api.mainnet-beta.solana.com".to_string(),
+            Cluster::Devnet => "https://api.devnet.solana.com".to_string(),
+            Cluster::Testnet => "https://api.testnet.solana.com".to_string(),
+            Cluster::Localnet => "http://127.0.0.1:8899".to_string(),
+            Cluster::Custom(url) => url.clone(),
+        }
+    }
+
+    fn commitment(&self) -> CommitmentConfig {
+        CommitmentConfig::confirmed()
+    }
+
+    fn client(&self) -> RpcClient {
+        RpcClient::new_with_commitment(self.rpc_url(), self.commitment())
+    }
+}
+
+impl FromStr for Cluster {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "mainnet" | "mainnet-beta" => Ok(Cluster::Mainnet),
+            "devnet" => Ok(Cluster::Devnet),
+            "testnet" => Ok(Cluster::Testnet),
+            "localnet" | "localhost" => Ok(Cluster::Localnet),
+            _ if s.starts_with("http://") || s.starts_with("https://") => {
+                Ok(Cluster::Custom(s.to_string()))
+            }
+            other => Err(format!("Unknown cluster: {}", other)),
+        }
+    }
+}
+
+/// Which SPL token program a request targets. Token-2022 mints (transfer
+/// fees, metadata, etc.) need their own instruction builders and
+/// extension-aware account parsing, so this is threaded through every
+/// create/transfer/query handler instead of hardcoding `spl_token::id()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TokenProgram {
+    #[default]
+    Token,
+    #[serde(rename = "token-2022")]
+    Token2022,
+}
+
+impl TokenProgram {
+    fn program_id(&self) -> Pubkey {
+        match self {
+            TokenProgram::Token => spl_token::id(),
+            TokenProgram::Token2022 => spl_token_2022::id(),
+        }
+    }
 }
 
 #[tokio::main]
@@ -40,16 +125,28 @@ async fn main() {
         }
     };
 
+    let cluster = match (&config.cluster, &config.rpc) {
+        (Some(cluster), _) => Cluster::from_str(cluster).unwrap_or_else(|e| panic!("{}", e)),
+        (None, Some(rpc)) => Cluster::Custom(rpc.to_string()),
+        (None, None) => panic!("no rpc url or cluster provided"),
+    };
+
     let shared_state = Arc::new(AppState {
-        client: Arc::new(RpcClient::new(config.rpc.to_string())),
+        client: Arc::new(cluster.client()),
+        cluster,
     });
 
     let app = axum::Router::new()
         .route("/keypair", axum::routing::post(keypair))
+        .route("/keypair/mnemonic", axum::routing::post(generate_mnemonic))
         .route("/token/create", axum::routing::post(create_token))
         .route("/message/sign", axum::routing::post(sign_message))
         .route("/message/verify", axum::routing::post(verify_message))
         .route("/send/token", axum::routing::post(send_spl_token))
+        .route("/transaction/simulate", axum::routing::post(simulate_transfer))
+        .route("/airdrop", axum::routing::post(airdrop))
+        .route("/token/accounts", axum::routing::post(get_token_accounts))
+        .route("/transaction/submit", axum::routing::post(submit_transaction))
         .with_state(shared_state);
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
@@ -58,72 +155,286 @@ async fn main() {
     println!("Hello, world!");
 }
 
-async fn keypair() -> impl IntoResponse {
-    let keypair = Keypair::new();
+#[derive(Deserialize, Default)]
+struct KeypairRequest {
+    #[serde(default)]
+    mnemonic: Option<String>,
+    #[serde(default)]
+    passphrase: Option<String>,
+    #[serde(default)]
+    account: Option<u32>,
+    #[serde(default)]
+    derivation_path: Option<String>,
+}
+
+async fn keypair(body: Option<Json<KeypairRequest>>) -> impl IntoResponse {
+    let request = body.map(|Json(r)| r).unwrap_or_default();
+
+    match request.mnemonic {
+        Some(phrase) => match derive_keypair_from_mnemonic(
+            &phrase,
+            request.passphrase.as_deref().unwrap_or(""),
+            request.account.unwrap_or(0),
+            request.derivation_path.as_deref(),
+        ) {
+            Ok((keypair, path)) => Json(json!({
+                "success": true,
+                "data": {
+                    "pubkey": keypair.try_pubkey().unwrap().to_string(),
+                    "secret": keypair.to_base58_string(),
+                    "path": path,
+                }
+            }))
+            .into_response(),
+            Err(e) => (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": e,
+                })),
+            )
+                .into_response(),
+        },
+        None => {
+            let keypair = Keypair::new();
+
+            Json(json!({
+                "success": true,
+                "data": {
+                    "pubkey": keypair.try_pubkey().unwrap().to_string(),
+                    "secret": keypair.to_base58_string(),
+                }
+            }))
+            .into_response()
+        }
+    }
+}
+
+async fn generate_mnemonic() -> impl IntoResponse {
+    let mnemonic = Mnemonic::new(MnemonicType::Words12, Language::English);
 
     Json(json!({
         "success": true,
         "data": {
-            "pubkey": keypair.try_pubkey().unwrap().to_string(),
-            "secret": keypair.to_base58_string(),
+            "mnemonic": mnemonic.phrase(),
         }
     }))
 }
 
+/// Derives a Solana keypair from a BIP39 mnemonic following SLIP-0010's
+/// ed25519 scheme (all path segments hardened), since ed25519 has no
+/// notion of non-hardened child keys.
+fn derive_keypair_from_mnemonic(
+    phrase: &str,
+    passphrase: &str,
+    account: u32,
+    derivation_path: Option<&str>,
+) -> Result<(Keypair, String), String> {
+    let mnemonic =
+        Mnemonic::from_phrase(phrase, Language::English).map_err(|_| "Invalid mnemonic phrase".to_string())?;
+    let seed = Seed::new(&mnemonic, passphrase);
+
+    let path = derivation_path
+        .map(|p| p.to_string())
+        .unwrap_or_else(|| format!("m/44'/501'/{}'/0'", account));
+    let indices = parse_derivation_path(&path)?;
+
+    let derived_seed = derive_slip10_ed25519_seed(seed.as_bytes(), &indices);
+
+    let secret = ed25519_dalek::SecretKey::from_bytes(&derived_seed)
+        .map_err(|_| "Failed to derive secret key from seed".to_string())?;
+    let public = ed25519_dalek::PublicKey::from(&secret);
+
+    let mut keypair_bytes = [0u8; 64];
+    keypair_bytes[..32].copy_from_slice(secret.as_bytes());
+    keypair_bytes[32..].copy_from_slice(public.as_bytes());
+
+    let keypair =
+        Keypair::from_bytes(&keypair_bytes).map_err(|_| "Failed to construct keypair".to_string())?;
+
+    Ok((keypair, path))
+}
+
+/// Parses a `m/44'/501'/0'/0'` style path into its hardened child indices.
+/// Only hardened segments are supported, matching ed25519's SLIP-0010 rules.
+fn parse_derivation_path(path: &str) -> Result<Vec<u32>, String> {
+    let mut segments = path.split('/');
+    if segments.next() != Some("m") {
+        return Err("Derivation path must start with 'm'".to_string());
+    }
+
+    segments
+        .map(|segment| {
+            if !(segment.ends_with('\'') || segment.ends_with('h')) {
+                return Err(format!(
+                    "Only hardened path segments are supported, got '{}'",
+                    segment
+                ));
+            }
+            segment
+                .trim_end_matches(['\'', 'h'])
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid path segment: {}", segment))
+        })
+        .collect()
+}
+
+/// SLIP-0010 ed25519 hierarchical derivation: master key from the seed, then
+/// one hardened HMAC-SHA512 step per path index.
+fn derive_slip10_ed25519_seed(seed: &[u8], path: &[u32]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha512>::new_from_slice(b"ed25519 seed").expect("HMAC accepts any key length");
+    mac.update(seed);
+    let (mut key, mut chain_code) = split_key_and_chain_code(&mac.finalize().into_bytes());
+
+    for &index in path {
+        let hardened_index = index | 0x80000000;
+        let mut mac =
+            Hmac::<Sha512>::new_from_slice(&chain_code).expect("HMAC accepts any key length");
+        mac.update(&[0u8]);
+        mac.update(&key);
+        mac.update(&hardened_index.to_be_bytes());
+        let (new_key, new_chain_code) = split_key_and_chain_code(&mac.finalize().into_bytes());
+        key = new_key;
+        chain_code = new_chain_code;
+    }
+
+    key
+}
+
+fn split_key_and_chain_code(bytes: &[u8]) -> ([u8; 32], [u8; 32]) {
+    let mut key = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    key.copy_from_slice(&bytes[0..32]);
+    chain_code.copy_from_slice(&bytes[32..64]);
+    (key, chain_code)
+}
+
 #[derive(Deserialize)]
 struct CreateTokenRequest {
     mint_authority: String,
     mint: String,
     decimals: i32,
+    #[serde(default)]
+    build_only: bool,
+    #[serde(default)]
+    payer: Option<String>,
+    #[serde(default)]
+    token_program: TokenProgram,
+    #[serde(default)]
+    cluster: Option<String>,
 }
 
 async fn create_token(
     State(state): State<Arc<AppState>>,
     Json(body): Json<CreateTokenRequest>,
 ) -> impl IntoResponse {
-    let payer = Keypair::new();
-    let mint_authority = Keypair::new();
-    let mint_keypair = Keypair::new();
+    if body.build_only {
+        return match build_create_token(&state, &body).await {
+            Ok((unsigned_transaction, required_signers)) => Json(json!({
+                "success": true,
+                "data": {
+                    "unsigned_transaction": unsigned_transaction,
+                    "required_signers": required_signers,
+                    "mint": body.mint,
+                }
+            }))
+            .into_response(),
+            Err(e) => (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "success": false,
+                    "error": e,
+                })),
+            )
+                .into_response(),
+        };
+    }
+
+    // This server never holds a funded payer's private key, so the only
+    // mode that actually works is build_only (producing an unsigned
+    // transaction for external/hardware-wallet signing) — see 6afac75,
+    // which made the same call for /send/token.
+    (
+        StatusCode::NOT_IMPLEMENTED,
+        Json(json!({
+            "success": false,
+            "error": "not_implemented",
+            "message": "Server-side signing is not supported; set build_only to get an unsigned transaction.",
+        })),
+    )
+        .into_response()
+}
+
+/// Builds the mint-creation instruction set but returns it unsigned for
+/// external signing, instead of generating throwaway server-side keypairs.
+async fn build_create_token(
+    state: &AppState,
+    body: &CreateTokenRequest,
+) -> Result<(String, Vec<String>), String> {
+    let mint_authority_pubkey =
+        Pubkey::from_str(&body.mint_authority).map_err(|_| "Invalid mint_authority address format".to_string())?;
+    let mint_pubkey = Pubkey::from_str(&body.mint).map_err(|_| "Invalid mint address format".to_string())?;
+    let payer_pubkey = match &body.payer {
+        Some(payer) => Pubkey::from_str(payer).map_err(|_| "Invalid payer address format".to_string())?,
+        None => mint_authority_pubkey,
+    };
+    let decimals = u8::try_from(body.decimals).map_err(|_| "decimals must be between 0 and 255".to_string())?;
+    let token_program_id = body.token_program.program_id();
+    let rpc_client =
+        resolve_client(state, &body.cluster).map_err(|(_, Json(e))| e.message)?;
 
-    // Use Mint::LEN constant (82 bytes)
-    let mint_rent = state
-        .client
-        .get_minimum_balance_for_rent_exemption(Mint::LEN)
-        .unwrap();
+    let mint_len = match body.token_program {
+        TokenProgram::Token => Mint::LEN,
+        TokenProgram::Token2022 => spl_token_2022::state::Mint::LEN,
+    };
+    let mint_rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(mint_len)
+        .map_err(|e| format!("Failed to fetch rent exemption: {}", e))?;
 
     let create_mint_account_ix = system_instruction::create_account(
-        &payer.pubkey(),
-        &mint_keypair.pubkey(),
+        &payer_pubkey,
+        &mint_pubkey,
         mint_rent,
-        Mint::LEN as u64, // Use Mint::LEN here
-        &spl_token::id(),
+        mint_len as u64,
+        &token_program_id,
     );
 
-    let init_mint_ix = token_instruction::initialize_mint(
-        &spl_token::id(),
-        &mint_keypair.pubkey(),
-        &mint_authority.pubkey(),
-        Some(&mint_authority.pubkey()),
-        9, // decimals
-    )
-    .unwrap();
-
-    let recent_blockhash = state.client.get_latest_blockhash().unwrap();
-    let transaction = Transaction::new_signed_with_payer(
-        &[create_mint_account_ix, init_mint_ix],
-        Some(&payer.pubkey()),
-        &[&payer, &mint_keypair],
-        recent_blockhash,
-    );
+    let init_mint_ix = match body.token_program {
+        TokenProgram::Token => token_instruction::initialize_mint(
+            &token_program_id,
+            &mint_pubkey,
+            &mint_authority_pubkey,
+            Some(&mint_authority_pubkey),
+            decimals,
+        )
+        .map_err(|e| format!("Failed to build initialize_mint instruction: {}", e))?,
+        TokenProgram::Token2022 => spl_token_2022::instruction::initialize_mint(
+            &token_program_id,
+            &mint_pubkey,
+            &mint_authority_pubkey,
+            Some(&mint_authority_pubkey),
+            decimals,
+        )
+        .map_err(|e| format!("Failed to build initialize_mint instruction: {}", e))?,
+    };
+
+    let recent_blockhash = rpc_client
+        .get_latest_blockhash()
+        .map_err(|e| format!("Failed to fetch recent blockhash: {}", e))?;
 
-    let signature = state
-        .client
-        .send_and_confirm_transaction(&transaction)
-        .unwrap();
-    println!("Token created! Signature: {}", signature);
-    println!("Mint address: {}", mint_keypair.pubkey());
+    let mut transaction =
+        Transaction::new_with_payer(&[create_mint_account_ix, init_mint_ix], Some(&payer_pubkey));
+    transaction.message.recent_blockhash = recent_blockhash;
 
-    Json(json!({}))
+    let unsigned_transaction = encode_unsigned_transaction(&transaction)
+        .map_err(|e| format!("Failed to encode transaction: {}", e))?;
+
+    let mut required_signers = vec![payer_pubkey.to_string()];
+    if mint_pubkey != payer_pubkey {
+        required_signers.push(mint_pubkey.to_string());
+    }
+
+    Ok((unsigned_transaction, required_signers))
 }
 
 #[derive(Deserialize)]
@@ -208,12 +519,46 @@ struct TransferRequest {
     mint: String,
     owner: String,
     amount: u64,
+    #[serde(default)]
+    simulate_only: bool,
+    #[serde(default)]
+    cluster: Option<String>,
+    #[serde(default)]
+    create_destination_if_missing: bool,
+    #[serde(default)]
+    build_only: bool,
+    #[serde(default)]
+    token_program: TokenProgram,
 }
 
-async fn send_spl_token(
-    Json(request): Json<TransferRequest>,
-) -> Result<Json<TransferResponse>, (StatusCode, Json<ErrorResponse>)> {
-    // Parse public keys from strings
+#[derive(serde::Serialize)]
+struct TransferResponse {
+    success: bool,
+    signature: Option<String>,
+    message: String,
+    created_destination_ata: bool,
+    unsigned_transaction: Option<String>,
+    required_signers: Option<Vec<String>>,
+}
+
+#[derive(serde::Serialize)]
+struct ErrorResponse {
+    error: String,
+    message: String,
+}
+
+#[derive(serde::Serialize)]
+struct SimulateResponse {
+    success: bool,
+    err: Option<String>,
+    logs: Option<Vec<String>>,
+    units_consumed: Option<u64>,
+    accounts: Option<Vec<serde_json::Value>>,
+}
+
+fn parse_transfer_pubkeys(
+    request: &TransferRequest,
+) -> Result<(Pubkey, Pubkey, Pubkey), (StatusCode, Json<ErrorResponse>)> {
     let destination_pubkey = Pubkey::from_str(&request.destination).map_err(|_| {
         (
             StatusCode::BAD_REQUEST,
@@ -244,69 +589,797 @@ async fn send_spl_token(
         )
     })?;
 
-    // Execute the transfer
-    match execute_spl_transfer(
+    Ok((destination_pubkey, mint_pubkey, owner_pubkey))
+}
+
+/// Resolves the cluster a request should target: its own `cluster` override
+/// if given, otherwise the app's configured cluster.
+fn resolve_cluster(
+    state: &AppState,
+    cluster_override: &Option<String>,
+) -> Result<Cluster, (StatusCode, Json<ErrorResponse>)> {
+    match cluster_override {
+        Some(cluster) => Cluster::from_str(cluster).map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "invalid_cluster".to_string(),
+                    message: e,
+                }),
+            )
+        }),
+        None => Ok(state.cluster.clone()),
+    }
+}
+
+/// Resolves the `RpcClient` a request should use: the request's own
+/// `cluster` override if given, otherwise the app's shared client. This is
+/// what keeps transfers from silently landing on a different network than
+/// the rest of the app.
+fn resolve_client(
+    state: &AppState,
+    cluster_override: &Option<String>,
+) -> Result<Arc<RpcClient>, (StatusCode, Json<ErrorResponse>)> {
+    let cluster = resolve_cluster(state, cluster_override)?;
+    if cluster == state.cluster {
+        Ok(state.client.clone())
+    } else {
+        Ok(Arc::new(cluster.client()))
+    }
+}
+
+async fn send_spl_token(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<TransferRequest>,
+) -> Result<Json<TransferResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let (destination_pubkey, mint_pubkey, owner_pubkey) = parse_transfer_pubkeys(&request)?;
+    let rpc_client = resolve_client(&state, &request.cluster)?;
+
+    if request.simulate_only {
+        let result = simulate_spl_transfer(
+            &rpc_client,
+            &owner_pubkey,
+            &destination_pubkey,
+            &mint_pubkey,
+            request.amount,
+            request.token_program,
+        )
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "simulation_failed".to_string(),
+                    message: format!("Simulation failed: {}", e),
+                }),
+            )
+        })?;
+
+        return Ok(Json(TransferResponse {
+            success: result.success,
+            signature: None,
+            message: match &result.err {
+                Some(err) => format!("Simulation failed: {}", err),
+                None => "Simulation succeeded, no transaction was broadcast".to_string(),
+            },
+            created_destination_ata: false,
+            unsigned_transaction: None,
+            required_signers: None,
+        }));
+    }
+
+    if request.build_only {
+        let (unsigned_transaction, required_signers, created_destination_ata) = build_spl_transfer(
+            &rpc_client,
+            &owner_pubkey,
+            &destination_pubkey,
+            &mint_pubkey,
+            request.amount,
+            request.create_destination_if_missing,
+            request.token_program,
+        )
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "build_failed".to_string(),
+                    message: format!("Failed to build transaction: {}", e),
+                }),
+            )
+        })?;
+
+        return Ok(Json(TransferResponse {
+            success: true,
+            signature: None,
+            message: "Transaction built, not broadcast".to_string(),
+            created_destination_ata,
+            unsigned_transaction: Some(unsigned_transaction),
+            required_signers: Some(required_signers),
+        }));
+    }
+
+    // This server never holds the owner's private key, so there is no
+    // signer to broadcast with here — `build_only` (producing an unsigned
+    // transaction for external/hardware-wallet signing) is the only
+    // non-simulation mode this endpoint actually supports.
+    Err((
+        StatusCode::NOT_IMPLEMENTED,
+        Json(ErrorResponse {
+            error: "not_implemented".to_string(),
+            message: "Server-side signing is not supported; set build_only to get an unsigned transaction, or simulate_only to preflight.".to_string(),
+        }),
+    ))
+}
+
+async fn simulate_transfer(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<TransferRequest>,
+) -> Result<Json<SimulateResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let (destination_pubkey, mint_pubkey, owner_pubkey) = parse_transfer_pubkeys(&request)?;
+    let rpc_client = resolve_client(&state, &request.cluster)?;
+
+    match simulate_spl_transfer(
+        &rpc_client,
         &owner_pubkey,
         &destination_pubkey,
         &mint_pubkey,
         request.amount,
+        request.token_program,
     )
     .await
     {
-        Ok(signature) => Ok(Json(json!( {
-            "success": true,
-            "signature": Some(signature),
-            "message": "Transfer completed successfully".to_string(),
-        }))),
+        Ok(result) => Ok(Json(result)),
         Err(e) => Err((
             StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json! ({
-                "error": "transfer_failed".to_string(),
-                "message": format!("Transfer failed: {}", e),
-            })),
+            Json(ErrorResponse {
+                error: "simulation_failed".to_string(),
+                message: format!("Simulation failed: {}", e),
+            }),
         )),
     }
 }
 
-async fn execute_spl_transfer(
+/// Reads a mint's decimals, unpacking through `StateWithExtensions` for
+/// Token-2022 mints so extension data (e.g. transfer fee configs) doesn't
+/// trip up the unpack. Only needed for Token-2022, since `transfer_checked`
+/// requires decimals while the classic `transfer` instruction doesn't.
+fn fetch_mint_decimals(
+    rpc_client: &RpcClient,
+    mint_pubkey: &Pubkey,
+) -> Result<u8, Box<dyn std::error::Error>> {
+    let mint_account = rpc_client.get_account(mint_pubkey)?;
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_account.data)?;
+    Ok(mint.base.decimals)
+}
+
+/// Builds the transfer instruction for either token program: classic
+/// `spl_token::transfer`, or `transfer_checked` for Token-2022, which needs
+/// the mint's decimals so transfer-fee mints settle correctly.
+fn build_transfer_instruction(
+    rpc_client: &RpcClient,
+    mint_pubkey: &Pubkey,
+    source_ata: &Pubkey,
+    destination_ata: &Pubkey,
+    owner_pubkey: &Pubkey,
+    amount: u64,
+    token_program: TokenProgram,
+) -> Result<solana_sdk::instruction::Instruction, Box<dyn std::error::Error>> {
+    let token_program_id = token_program.program_id();
+    match token_program {
+        TokenProgram::Token => Ok(transfer(
+            &token_program_id,
+            source_ata,
+            destination_ata,
+            owner_pubkey,
+            &[],
+            amount,
+        )?),
+        TokenProgram::Token2022 => {
+            let decimals = fetch_mint_decimals(rpc_client, mint_pubkey)?;
+            Ok(spl_token_2022::instruction::transfer_checked(
+                &token_program_id,
+                source_ata,
+                mint_pubkey,
+                destination_ata,
+                owner_pubkey,
+                &[],
+                amount,
+                decimals,
+            )?)
+        }
+    }
+}
+
+async fn simulate_spl_transfer(
+    rpc_client: &RpcClient,
+    owner_pubkey: &Pubkey,
+    destination_pubkey: &Pubkey,
+    mint_pubkey: &Pubkey,
+    amount: u64,
+    token_program: TokenProgram,
+) -> Result<SimulateResponse, Box<dyn std::error::Error>> {
+    let token_program_id = token_program.program_id();
+    let source_ata =
+        get_associated_token_address_with_program_id(owner_pubkey, mint_pubkey, &token_program_id);
+    let destination_ata = get_associated_token_address_with_program_id(
+        destination_pubkey,
+        mint_pubkey,
+        &token_program_id,
+    );
+
+    let transfer_instruction =
+        build_transfer_instruction(rpc_client, mint_pubkey, &source_ata, &destination_ata, owner_pubkey, amount, token_program)?;
+
+    let recent_blockhash = rpc_client.get_latest_blockhash()?;
+    let mut transaction = Transaction::new_with_payer(&[transfer_instruction], Some(owner_pubkey));
+    transaction.message.recent_blockhash = recent_blockhash;
+
+    let response = rpc_client.simulate_transaction(&transaction)?;
+    let RpcSimulateTransactionResult {
+        err,
+        logs,
+        units_consumed,
+        accounts,
+        ..
+    } = response.value;
+
+    Ok(SimulateResponse {
+        success: err.is_none(),
+        err: err.map(|e| e.to_string()),
+        logs,
+        units_consumed,
+        accounts: accounts
+            .map(|accs| accs.into_iter().map(|a| json!(a)).collect::<Vec<_>>()),
+    })
+}
+
+/// Serializes a transaction (signed or not) to the bincode+base64 wire
+/// format clients are expected to post back to `/transaction/submit`.
+fn encode_unsigned_transaction(transaction: &Transaction) -> Result<String, Box<dyn std::error::Error>> {
+    let bytes = bincode::serialize(transaction)?;
+    Ok(base64::engine::general_purpose::STANDARD.encode(bytes))
+}
+
+/// Builds the transfer instruction set (optionally preceded by creation of
+/// the destination ATA) and returns it as an unsigned, base64-encoded
+/// transaction instead of signing and broadcasting it server-side, for
+/// hardware-wallet / KMS signing flows.
+async fn build_spl_transfer(
+    rpc_client: &RpcClient,
     owner_pubkey: &Pubkey,
     destination_pubkey: &Pubkey,
     mint_pubkey: &Pubkey,
     amount: u64,
-) -> Result<String, Box<dyn std::error::Error>> {
-    // Initialize RPC client (use your preferred endpoint)
-    let rpc_client = RpcClient::new_with_commitment(
-        "https://api.devnet.solana.com".to_string(),
-        CommitmentConfig::confirmed(),
+    create_destination_if_missing: bool,
+    token_program: TokenProgram,
+) -> Result<(String, Vec<String>, bool), Box<dyn std::error::Error>> {
+    let token_program_id = token_program.program_id();
+    let source_ata =
+        get_associated_token_address_with_program_id(owner_pubkey, mint_pubkey, &token_program_id);
+    let destination_ata = get_associated_token_address_with_program_id(
+        destination_pubkey,
+        mint_pubkey,
+        &token_program_id,
     );
 
-    // Get associated token accounts
-    let source_ata = get_associated_token_address(owner_pubkey, mint_pubkey);
-    let destination_ata = get_associated_token_address(destination_pubkey, mint_pubkey);
+    let mut instructions = Vec::new();
+    let mut created_destination_ata = false;
+
+    if create_destination_if_missing {
+        let destination_exists = rpc_client
+            .get_account_with_commitment(&destination_ata, CommitmentConfig::confirmed())?
+            .value
+            .is_some();
+        if !destination_exists {
+            instructions.push(create_associated_token_account(
+                owner_pubkey,
+                destination_pubkey,
+                mint_pubkey,
+                &token_program_id,
+            ));
+            created_destination_ata = true;
+        }
+    }
 
-    // Create transfer instruction
-    let transfer_instruction = transfer(
-        &spl_token::id(),
+    instructions.push(build_transfer_instruction(
+        rpc_client,
+        mint_pubkey,
         &source_ata,
         &destination_ata,
         owner_pubkey,
-        &[],
         amount,
-    )?;
+        token_program,
+    )?);
 
-    // Note: In a real implementation, you would need to handle signing
-    // This is a simplified example - you'd need proper key management
     let recent_blockhash = rpc_client.get_latest_blockhash()?;
+    let mut transaction = Transaction::new_with_payer(&instructions, Some(owner_pubkey));
+    transaction.message.recent_blockhash = recent_blockhash;
 
-    // You would need to implement proper signing mechanism here
-    // This could involve hardware wallets, key management services, etc.
-    let transaction = Transaction::new_signed_with_payer(
-        &[transfer_instruction],
-        Some(owner_pubkey),
-        &[], // Signers would go here
-        recent_blockhash,
-    );
+    let unsigned_transaction = encode_unsigned_transaction(&transaction)?;
+    Ok((
+        unsigned_transaction,
+        vec![owner_pubkey.to_string()],
+        created_destination_ata,
+    ))
+}
+
+#[derive(Deserialize)]
+struct AirdropRequest {
+    pubkey: String,
+    lamports: u64,
+    #[serde(default)]
+    cluster: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct AirdropResponse {
+    success: bool,
+    signature: String,
+    balance: u64,
+}
+
+const AIRDROP_CONFIRMATION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+const AIRDROP_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+async fn airdrop(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<AirdropRequest>,
+) -> Result<Json<AirdropResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let pubkey = Pubkey::from_str(&request.pubkey).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "invalid_pubkey".to_string(),
+                message: "Invalid pubkey format".to_string(),
+            }),
+        )
+    })?;
+
+    let cluster = resolve_cluster(&state, &request.cluster)?;
+    if cluster == Cluster::Mainnet {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "mainnet_not_supported".to_string(),
+                message: "Airdrops are not available on mainnet".to_string(),
+            }),
+        ));
+    }
+
+    let rpc_client = resolve_client(&state, &request.cluster)?;
+
+    let signature = rpc_client
+        .request_airdrop(&pubkey, request.lamports)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "airdrop_failed".to_string(),
+                    message: format!("Airdrop request failed: {}", e),
+                }),
+            )
+        })?;
+
+    let start = std::time::Instant::now();
+    loop {
+        if rpc_client.confirm_transaction(&signature).unwrap_or(false) {
+            break;
+        }
+        if start.elapsed() > AIRDROP_CONFIRMATION_TIMEOUT {
+            return Err((
+                StatusCode::GATEWAY_TIMEOUT,
+                Json(ErrorResponse {
+                    error: "airdrop_timeout".to_string(),
+                    message: "Timed out waiting for airdrop confirmation".to_string(),
+                }),
+            ));
+        }
+        tokio::time::sleep(AIRDROP_POLL_INTERVAL).await;
+    }
+
+    let balance = rpc_client.get_balance(&pubkey).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                error: "balance_fetch_failed".to_string(),
+                message: format!("Airdrop confirmed but balance fetch failed: {}", e),
+            }),
+        )
+    })?;
+
+    Ok(Json(AirdropResponse {
+        success: true,
+        signature: signature.to_string(),
+        balance,
+    }))
+}
+
+#[derive(Deserialize)]
+struct TokenAccountsRequest {
+    owner: String,
+    #[serde(default)]
+    mint: Option<String>,
+    #[serde(default)]
+    cluster: Option<String>,
+    #[serde(default)]
+    token_program: TokenProgram,
+}
+
+#[derive(serde::Serialize)]
+struct TokenAccountEntry {
+    address: String,
+    mint: String,
+    amount: u64,
+    decimals: u8,
+    ui_amount: String,
+    extensions: Vec<String>,
+}
+
+#[derive(serde::Serialize)]
+struct TokenAccountsResponse {
+    success: bool,
+    accounts: Vec<TokenAccountEntry>,
+}
+
+async fn get_token_accounts(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<TokenAccountsRequest>,
+) -> Result<Json<TokenAccountsResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let owner_pubkey = Pubkey::from_str(&request.owner).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "invalid_owner".to_string(),
+                message: "Invalid owner address format".to_string(),
+            }),
+        )
+    })?;
+
+    let mint_filter = request
+        .mint
+        .as_deref()
+        .map(Pubkey::from_str)
+        .transpose()
+        .map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "invalid_mint".to_string(),
+                    message: "Invalid mint address format".to_string(),
+                }),
+            )
+        })?;
 
-    let signature = rpc_client.send_and_confirm_transaction(&transaction)?;
-    Ok(signature.to_string())
+    let rpc_client = resolve_client(&state, &request.cluster)?;
+    let token_program_id = request.token_program.program_id();
+
+    let filter = match mint_filter {
+        Some(mint) => TokenAccountsFilter::Mint(mint),
+        None => TokenAccountsFilter::ProgramId(token_program_id),
+    };
+
+    let keyed_accounts = rpc_client
+        .get_token_accounts_by_owner(&owner_pubkey, filter)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "fetch_failed".to_string(),
+                    message: format!("Failed to fetch token accounts: {}", e),
+                }),
+            )
+        })?;
+
+    // Cache mint decimals/extensions so the same mint isn't fetched more than
+    // once per request, mirroring the `mint_decimals` cache the Solana RPC's
+    // `parsed_token_accounts` module builds while listing owner accounts.
+    let mut mint_info: HashMap<Pubkey, (u8, Vec<String>)> = HashMap::new();
+    let mut accounts = Vec::with_capacity(keyed_accounts.len());
+
+    for keyed_account in keyed_accounts {
+        let data = match keyed_account.account.data {
+            UiAccountData::Binary(encoded, UiAccountEncoding::Base64) => base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .map_err(|e| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ErrorResponse {
+                            error: "decode_failed".to_string(),
+                            message: format!("Failed to decode token account data: {}", e),
+                        }),
+                    )
+                })?,
+            _ => {
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse {
+                        error: "unsupported_encoding".to_string(),
+                        message: "Expected base64-encoded token account data".to_string(),
+                    }),
+                ));
+            }
+        };
+
+        let (mint, amount) = match request.token_program {
+            TokenProgram::Token => {
+                let token_account = spl_token::state::Account::unpack(&data).map_err(|e| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ErrorResponse {
+                            error: "unpack_failed".to_string(),
+                            message: format!("Failed to unpack token account: {}", e),
+                        }),
+                    )
+                })?;
+                (token_account.mint, token_account.amount)
+            }
+            TokenProgram::Token2022 => {
+                let token_account =
+                    StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data).map_err(|e| {
+                        (
+                            StatusCode::INTERNAL_SERVER_ERROR,
+                            Json(ErrorResponse {
+                                error: "unpack_failed".to_string(),
+                                message: format!("Failed to unpack token account: {}", e),
+                            }),
+                        )
+                    })?;
+                (token_account.base.mint, token_account.base.amount)
+            }
+        };
+
+        let (decimals, extensions) = match mint_info.get(&mint) {
+            Some(info) => info.clone(),
+            None => {
+                let mint_account = rpc_client.get_account(&mint).map_err(|e| {
+                    (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        Json(ErrorResponse {
+                            error: "mint_fetch_failed".to_string(),
+                            message: format!("Failed to fetch mint account: {}", e),
+                        }),
+                    )
+                })?;
+
+                let info = match request.token_program {
+                    TokenProgram::Token => {
+                        let mint_state = Mint::unpack(&mint_account.data).map_err(|e| {
+                            (
+                                StatusCode::INTERNAL_SERVER_ERROR,
+                                Json(ErrorResponse {
+                                    error: "mint_unpack_failed".to_string(),
+                                    message: format!("Failed to unpack mint account: {}", e),
+                                }),
+                            )
+                        })?;
+                        (mint_state.decimals, Vec::new())
+                    }
+                    TokenProgram::Token2022 => {
+                        let mint_state =
+                            StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&mint_account.data)
+                                .map_err(|e| {
+                                    (
+                                        StatusCode::INTERNAL_SERVER_ERROR,
+                                        Json(ErrorResponse {
+                                            error: "mint_unpack_failed".to_string(),
+                                            message: format!("Failed to unpack mint account: {}", e),
+                                        }),
+                                    )
+                                })?;
+                        let extensions = mint_state
+                            .get_extension_types()
+                            .map_err(|e| {
+                                (
+                                    StatusCode::INTERNAL_SERVER_ERROR,
+                                    Json(ErrorResponse {
+                                        error: "mint_extensions_failed".to_string(),
+                                        message: format!("Failed to read mint extensions: {}", e),
+                                    }),
+                                )
+                            })?
+                            .iter()
+                            .map(|ext| format!("{:?}", ext))
+                            .collect();
+                        (mint_state.base.decimals, extensions)
+                    }
+                };
+
+                mint_info.insert(mint, info.clone());
+                info
+            }
+        };
+
+        accounts.push(TokenAccountEntry {
+            address: keyed_account.pubkey,
+            mint: mint.to_string(),
+            amount,
+            decimals,
+            ui_amount: format_ui_amount(amount, decimals),
+            extensions,
+        });
+    }
+
+    Ok(Json(TokenAccountsResponse {
+        success: true,
+        accounts,
+    }))
+}
+
+/// Renders a raw token amount at its mint's decimal scale as a fixed-point
+/// string, so clients get both the exact `u64` and a human value without
+/// floating point ever entering the picture.
+fn format_ui_amount(amount: u64, decimals: u8) -> String {
+    if decimals == 0 {
+        return amount.to_string();
+    }
+    // `decimals` comes straight off on-chain mint data, which the token
+    // program doesn't bound, so 10^decimals can overflow u64 (decimals >= 20).
+    // Fall back to the raw amount rather than panicking/wrapping in that case.
+    let Some(divisor) = 10u64.checked_pow(decimals as u32) else {
+        return amount.to_string();
+    };
+    let whole = amount / divisor;
+    let fraction = amount % divisor;
+    format!("{}.{:0width$}", whole, fraction, width = decimals as usize)
+}
+
+fn parse_commitment_level(s: &str) -> Result<CommitmentLevel, String> {
+    match s.to_lowercase().as_str() {
+        "processed" => Ok(CommitmentLevel::Processed),
+        "confirmed" => Ok(CommitmentLevel::Confirmed),
+        "finalized" => Ok(CommitmentLevel::Finalized),
+        other => Err(format!("Unknown commitment level: {}", other)),
+    }
+}
+
+#[derive(Deserialize)]
+struct SubmitTransactionRequest {
+    transaction: String,
+    #[serde(default)]
+    skip_preflight: bool,
+    #[serde(default)]
+    preflight_commitment: Option<String>,
+    #[serde(default)]
+    max_retries: Option<usize>,
+    #[serde(default)]
+    cluster: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct SubmitTransactionResponse {
+    success: bool,
+    signature: String,
+}
+
+/// Accepts a base64, client-signed transaction (as produced by the
+/// `build_only` transfer/create-token modes) and broadcasts it, so hardware
+/// wallets / KMS-backed signers never have to hand a secret key to this
+/// server.
+async fn submit_transaction(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<SubmitTransactionRequest>,
+) -> Result<Json<SubmitTransactionResponse>, (StatusCode, Json<ErrorResponse>)> {
+    let rpc_client = resolve_client(&state, &request.cluster)?;
+
+    let transaction_bytes = base64::engine::general_purpose::STANDARD
+        .decode(&request.transaction)
+        .map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "invalid_transaction".to_string(),
+                    message: format!("Failed to decode base64 transaction: {}", e),
+                }),
+            )
+        })?;
+
+    let transaction: Transaction = bincode::deserialize(&transaction_bytes).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                error: "invalid_transaction".to_string(),
+                message: format!("Failed to deserialize transaction: {}", e),
+            }),
+        )
+    })?;
+
+    let preflight_commitment = request
+        .preflight_commitment
+        .as_deref()
+        .map(parse_commitment_level)
+        .transpose()
+        .map_err(|e| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    error: "invalid_commitment".to_string(),
+                    message: e,
+                }),
+            )
+        })?;
+
+    let config = RpcSendTransactionConfig {
+        skip_preflight: request.skip_preflight,
+        preflight_commitment,
+        max_retries: request.max_retries,
+        ..RpcSendTransactionConfig::default()
+    };
+
+    let signature = rpc_client
+        .send_transaction_with_config(&transaction, config)
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(ErrorResponse {
+                    error: "submit_failed".to_string(),
+                    message: format!("Failed to submit transaction: {}", e),
+                }),
+            )
+        })?;
+
+    Ok(Json(SubmitTransactionResponse {
+        success: true,
+        signature: signature.to_string(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pins SLIP-0010/BIP-39 derivation to a known mnemonic -> pubkey vector
+    /// so a future change to the HMAC-SHA512 master/child math fails a test
+    /// instead of silently sending funds to the wrong address.
+    #[test]
+    fn derive_keypair_from_mnemonic_matches_known_vector() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+        let (keypair, path) =
+            derive_keypair_from_mnemonic(phrase, "", 0, Some("m/44'/501'/0'/0'")).unwrap();
+
+        assert_eq!(path, "m/44'/501'/0'/0'");
+        assert_eq!(
+            keypair.pubkey().to_string(),
+            "HAgk14JpMQLgt6rVgv7cBQFJWFto5Dqxi472uT3DKpqk"
+        );
+    }
+
+    #[test]
+    fn format_ui_amount_falls_back_on_overflowing_decimals() {
+        // 10^20 overflows u64, so this must not panic or wrap to a garbage
+        // divisor; it should fall back to the raw amount.
+        assert_eq!(format_ui_amount(123, 20), "123");
+        assert_eq!(format_ui_amount(123, 255), "123");
+    }
+
+    #[test]
+    fn format_ui_amount_formats_normal_decimals() {
+        assert_eq!(format_ui_amount(123456, 6), "0.123456");
+        assert_eq!(format_ui_amount(123456, 0), "123456");
+    }
+
+    #[test]
+    fn cluster_from_str_rejects_unknown_names() {
+        assert!(Cluster::from_str("not-a-real-cluster").is_err());
+    }
+
+    #[test]
+    fn cluster_from_str_accepts_known_names() {
+        assert_eq!(Cluster::from_str("devnet").unwrap(), Cluster::Devnet);
+        assert_eq!(Cluster::from_str("mainnet").unwrap(), Cluster::Mainnet);
+    }
+
+    #[test]
+    fn parse_derivation_path_rejects_non_hardened_segments() {
+        assert!(parse_derivation_path("m/44/501'/0'/0'").is_err());
+    }
+
+    #[test]
+    fn parse_derivation_path_accepts_hardened_segments() {
+        assert_eq!(
+            parse_derivation_path("m/44'/501'/0'/0'").unwrap(),
+            vec![44, 501, 0, 0]
+        );
+    }
 }